@@ -1,17 +1,44 @@
 #![no_std]
 #![no_main]
 
+mod morse;
+mod pattern;
+
+#[cfg(feature = "rtt")]
+use rtt_target::{rprintln, rtt_init_print};
+
+use core::cell::RefCell;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use cortex_m::interrupt::Mutex;
 use panic_halt as _;
 use cortex_m_rt::entry;
 use stm32f4xx_hal::{
-    pac,
+    gpio::{Edge, Input, Pin},
+    interrupt, pac,
     prelude::*,
+    timer::{Channel, Channel1},
 };
 
+/// Number of patterns the button cycles through.
+const NUM_SEQUENCES: usize = 5;
+
+/// Index of the pattern the main loop should run, advanced by the button ISR.
+static SEQUENCE_INDEX: AtomicUsize = AtomicUsize::new(0);
+
+/// The button pin, handed off to the EXTI0 interrupt handler.
+static BUTTON: Mutex<RefCell<Option<Pin<'A', 0, Input>>>> = Mutex::new(RefCell::new(None));
+
+/// Roughly 30 ms of busy-wait at 84 MHz, used to debounce the button in the ISR.
+const DEBOUNCE_CYCLES: u32 = 84_000 * 30;
+
 #[entry]
 fn main() -> ! {
+    #[cfg(feature = "rtt")]
+    rtt_init_print!();
+
     // Get access to the device specific peripherals
-    let dp = pac::Peripherals::take().unwrap();
+    let mut dp = pac::Peripherals::take().unwrap();
     let cp = cortex_m::Peripherals::take().unwrap();
 
     // Set up the system clock to 84 MHz
@@ -25,83 +52,152 @@ fn main() -> ! {
     let gpioc = dp.GPIOC.split();
     let mut led = gpioc.pc13.into_push_pull_output();
 
-    let mut sequence_index = 0;
+    // PC13 is not a timer output on this part, so the breathing pattern gets
+    // its own PWM-capable LED pin (TIM3 CH1 on PB4).
+    let gpiob = dp.GPIOB.split();
+    let pwm_pin = Channel1::new(gpiob.pb4.into_alternate::<2>());
+    let mut pwm = dp.TIM3.pwm_hz(pwm_pin, 1.kHz(), &clocks);
+    let pwm_channel = Channel::C1;
+    pwm.enable(pwm_channel);
+
+    // A bank of LEDs for the chaser sequence, erased to a uniform type so
+    // they can live together in an array. PA0 is reserved for the onboard
+    // KEY button below, so the chaser starts at PA4 instead.
+    let gpioa = dp.GPIOA.split();
+    let mut chaser_leds = [
+        gpioa.pa4.into_push_pull_output().erase(),
+        gpioa.pa5.into_push_pull_output().erase(),
+        gpioa.pa6.into_push_pull_output().erase(),
+        gpioa.pa7.into_push_pull_output().erase(),
+    ];
+    let num_chaser_leds = chaser_leds.len();
+    for chaser_led in chaser_leds.iter_mut() {
+        chaser_led.set_high(); // start with all chaser LEDs off
+    }
+
+    // Onboard KEY button on PA0, wired through EXTI0 so a press advances
+    // the pattern.
+    let mut syscfg = dp.SYSCFG.constrain();
+    let mut button = gpioa.pa0.into_pull_up_input();
+    button.make_interrupt_source(&mut syscfg);
+    button.trigger_on_edge(&mut dp.EXTI, Edge::Falling);
+    button.enable_interrupt(&mut dp.EXTI);
+
+    cortex_m::interrupt::free(|cs| {
+        BUTTON.borrow(cs).replace(Some(button));
+    });
+    unsafe {
+        cortex_m::peripheral::NVIC::unmask(pac::Interrupt::EXTI0);
+    }
+
+    let mut logged_sequence_index = None;
 
     loop {
+        let sequence_index = SEQUENCE_INDEX.load(Ordering::SeqCst);
+        if logged_sequence_index != Some(sequence_index) {
+            log_pattern(pattern_name(sequence_index));
+            logged_sequence_index = Some(sequence_index);
+        }
+
         match sequence_index {
-            // Fast blink sequence
-            0 => {
-                for _ in 0..3 {
-                    led.set_low(); // LED ON (inverted logic on most boards)
-                    delay.delay_ms(100_u32);
-                    led.set_high(); // LED OFF
+            // Fast blink, slow blink and SOS are all table-driven patterns.
+            0..=2 => pattern::run_pattern(&mut led, &mut delay, &pattern::PATTERNS[sequence_index]),
+
+            // Knight-Rider-style chaser across the GPIOA LED bank. This
+            // drives four pins at once, so it doesn't fit the single-LED
+            // `Step` model above and stays special-cased here.
+            3 => {
+                for step in 0..(2 * num_chaser_leds) {
+                    let position = if step < num_chaser_leds {
+                        step
+                    } else {
+                        2 * num_chaser_leds - step - 1
+                    };
+                    // The LED lit on the step before: one to the left on the
+                    // way out, one to the right on the way back.
+                    let previous = if step < num_chaser_leds {
+                        (position + num_chaser_leds - 1) % num_chaser_leds
+                    } else {
+                        (position + 1) % num_chaser_leds
+                    };
+                    chaser_leds[previous].set_high();
+                    chaser_leds[position].set_low();
                     delay.delay_ms(100_u32);
                 }
-                delay.delay_ms(1000_u32); // Pause between sequences
-            },
-            
-            // Slow blink sequence
-            1 => {
-                for _ in 0..2 {
-                    led.set_low(); // LED ON
-                    delay.delay_ms(500_u32);
-                    led.set_high(); // LED OFF
-                    delay.delay_ms(500_u32);
-                }
-                delay.delay_ms(1000_u32); // Pause between sequences
-            },
-            
-            // SOS pattern
-            2 => {
-                // S - three short blinks
-                for _ in 0..3 {
-                    led.set_low();
-                    delay.delay_ms(200_u32);
-                    led.set_high();
-                    delay.delay_ms(200_u32);
-                }
-                delay.delay_ms(200_u32);
-                
-                // O - three long blinks
-                for _ in 0..3 {
-                    led.set_low();
-                    delay.delay_ms(600_u32);
-                    led.set_high();
-                    delay.delay_ms(200_u32);
-                }
-                delay.delay_ms(200_u32);
-                
-                // S - three short blinks
-                for _ in 0..3 {
-                    led.set_low();
-                    delay.delay_ms(200_u32);
-                    led.set_high();
-                    delay.delay_ms(200_u32);
-                }
-                delay.delay_ms(2000_u32); // Long pause after SOS
+                chaser_leds[0].set_high(); // the final step above leaves LED 0 lit
+                delay.delay_ms(500_u32);
             },
-            
-            // Breathing pattern
+
+            // Breathing pattern, driven by real PWM on the TIM3 LED pin
             _ => {
-                // Fade in effect (simulated with PWM-like blinking)
-                for i in 1..=10 {
-                    led.set_low();
-                    delay.delay_ms((i * 10) as u32);
-                    led.set_high();
-                    delay.delay_ms((100 - (i * 10)) as u32);
+                let max_duty = pwm.get_max_duty();
+
+                // Fade in
+                for step in 0..=100 {
+                    let duty = (max_duty as u32 * step / 100) as u16;
+                    pwm.set_duty(pwm_channel, duty);
+                    delay.delay_ms(5_u32);
                 }
-                // Fade out effect
-                for i in (1..=10).rev() {
-                    led.set_low();
-                    delay.delay_ms((i * 10) as u32);
-                    led.set_high();
-                    delay.delay_ms((100 - (i * 10)) as u32);
+                // Fade out
+                for step in (0..=100).rev() {
+                    let duty = (max_duty as u32 * step / 100) as u16;
+                    pwm.set_duty(pwm_channel, duty);
+                    delay.delay_ms(5_u32);
                 }
                 delay.delay_ms(500_u32);
             }
         }
-        
-        // Move to next sequence
-        sequence_index = (sequence_index + 1) % 4;
+    }
+}
+
+/// Human-readable name for a sequence index, used for status logging.
+fn pattern_name(sequence_index: usize) -> &'static str {
+    match sequence_index {
+        0 => "fast blink",
+        1 => "slow blink",
+        2 => "SOS",
+        3 => "chaser",
+        _ => "breathing",
+    }
+}
+
+/// Logs the active pattern over RTT when the `rtt` feature is enabled; a
+/// no-op otherwise so release builds stay silent and small.
+#[cfg(feature = "rtt")]
+fn log_pattern(name: &str) {
+    rprintln!("pattern: {}", name);
+}
+
+#[cfg(not(feature = "rtt"))]
+fn log_pattern(_name: &str) {}
+
+/// Fires on a falling edge on PA0 (KEY button press). Debounces in software
+/// and advances `SEQUENCE_INDEX` so the main loop picks up the next pattern.
+#[interrupt]
+fn EXTI0() {
+    cortex_m::interrupt::free(|cs| {
+        if let Some(button) = BUTTON.borrow(cs).borrow_mut().as_mut() {
+            button.clear_interrupt_pending_bit();
+        }
+    });
+
+    // Debounce outside the critical section above, so other interrupts
+    // aren't blocked for the duration of the busy-wait.
+    cortex_m::asm::delay(DEBOUNCE_CYCLES);
+
+    let still_pressed = cortex_m::interrupt::free(|cs| {
+        BUTTON
+            .borrow(cs)
+            .borrow()
+            .as_ref()
+            .is_some_and(|button| button.is_low())
+    });
+
+    if still_pressed {
+        SEQUENCE_INDEX
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |i| {
+                Some((i + 1) % NUM_SEQUENCES)
+            })
+            .ok();
     }
 }
\ No newline at end of file
@@ -0,0 +1,127 @@
+//! Allocation-free Morse-code encoding and LED playback.
+
+use stm32f4xx_hal::gpio::{Output, Pin};
+
+/// Base timing unit: a dot is one unit, a dash is three.
+const UNIT_MS: u32 = 150;
+
+/// A single Morse symbol: a dot (`.`) or a dash (`-`).
+#[derive(Clone, Copy)]
+enum Symbol {
+    Dot,
+    Dash,
+}
+
+/// Lookup table for `A`-`Z`, indexed by `c - b'A'`.
+/// Each entry is the symbol sequence, padded with `None`.
+const LETTERS: [[Option<Symbol>; 4]; 26] = {
+    use Symbol::{Dash, Dot};
+    [
+        [Some(Dot), Some(Dash), None, None],             // A
+        [Some(Dash), Some(Dot), Some(Dot), Some(Dot)],   // B
+        [Some(Dash), Some(Dot), Some(Dash), Some(Dot)],  // C
+        [Some(Dash), Some(Dot), Some(Dot), None],        // D
+        [Some(Dot), None, None, None],                   // E
+        [Some(Dot), Some(Dot), Some(Dash), Some(Dot)],   // F
+        [Some(Dash), Some(Dash), Some(Dot), None],       // G
+        [Some(Dot), Some(Dot), Some(Dot), Some(Dot)],    // H
+        [Some(Dot), Some(Dot), None, None],              // I
+        [Some(Dot), Some(Dash), Some(Dash), Some(Dash)], // J
+        [Some(Dash), Some(Dot), Some(Dash), None],       // K
+        [Some(Dot), Some(Dash), Some(Dot), Some(Dot)],   // L
+        [Some(Dash), Some(Dash), None, None],            // M
+        [Some(Dash), Some(Dot), None, None],             // N
+        [Some(Dash), Some(Dash), Some(Dash), None],      // O
+        [Some(Dot), Some(Dash), Some(Dash), Some(Dot)],  // P
+        [Some(Dash), Some(Dash), Some(Dot), Some(Dash)], // Q
+        [Some(Dot), Some(Dash), Some(Dot), None],        // R
+        [Some(Dot), Some(Dot), Some(Dot), None],         // S
+        [Some(Dash), None, None, None],                  // T
+        [Some(Dot), Some(Dot), Some(Dash), None],        // U
+        [Some(Dot), Some(Dot), Some(Dot), Some(Dash)],   // V
+        [Some(Dot), Some(Dash), Some(Dash), None],       // W
+        [Some(Dash), Some(Dot), Some(Dot), Some(Dash)],  // X
+        [Some(Dash), Some(Dot), Some(Dash), Some(Dash)], // Y
+        [Some(Dash), Some(Dash), Some(Dot), Some(Dot)],  // Z
+    ]
+};
+
+/// Lookup table for `0`-`9`, indexed by `c - b'0'`.
+const DIGITS: [[Symbol; 5]; 10] = {
+    use Symbol::{Dash, Dot};
+    [
+        [Dash, Dash, Dash, Dash, Dash], // 0
+        [Dot, Dash, Dash, Dash, Dash],  // 1
+        [Dot, Dot, Dash, Dash, Dash],   // 2
+        [Dot, Dot, Dot, Dash, Dash],    // 3
+        [Dot, Dot, Dot, Dot, Dash],     // 4
+        [Dot, Dot, Dot, Dot, Dot],      // 5
+        [Dash, Dot, Dot, Dot, Dot],     // 6
+        [Dash, Dash, Dot, Dot, Dot],    // 7
+        [Dash, Dash, Dash, Dot, Dot],   // 8
+        [Dash, Dash, Dash, Dash, Dot],  // 9
+    ]
+};
+
+/// Blinks `msg` out on `led` as Morse code.
+///
+/// Unknown characters (anything but `A`-`Z`, `a`-`z`, `0`-`9` and spaces)
+/// are skipped. Spaces are treated as word gaps.
+pub fn blink_morse<const P: char, const N: u8>(
+    led: &mut Pin<P, N, Output>,
+    delay: &mut impl embedded_hal::delay::DelayNs,
+    msg: &str,
+) {
+    let mut chars = msg.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c.to_ascii_uppercase() {
+            ' ' => delay.delay_ms(7 * UNIT_MS), // word gap
+            letter @ 'A'..='Z' => {
+                let symbols = LETTERS[letter as usize - 'A' as usize];
+                play_symbols(led, delay, symbols.iter().flatten().copied());
+                if !matches!(chars.peek(), None | Some(' ')) {
+                    delay.delay_ms(3 * UNIT_MS); // inter-character gap
+                }
+            }
+            digit @ '0'..='9' => {
+                let symbols = DIGITS[digit as usize - '0' as usize];
+                play_symbols(led, delay, symbols.into_iter());
+                if !matches!(chars.peek(), None | Some(' ')) {
+                    delay.delay_ms(3 * UNIT_MS); // inter-character gap
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Plays a character's symbols, with a 1-unit gap between them but not
+/// trailing the last one (the caller adds whatever gap follows the character).
+fn play_symbols<const P: char, const N: u8>(
+    led: &mut Pin<P, N, Output>,
+    delay: &mut impl embedded_hal::delay::DelayNs,
+    symbols: impl Iterator<Item = Symbol>,
+) {
+    let mut symbols = symbols.peekable();
+    while let Some(symbol) = symbols.next() {
+        play_symbol(led, delay, symbol);
+        if symbols.peek().is_some() {
+            delay.delay_ms(UNIT_MS); // intra-character gap
+        }
+    }
+}
+
+fn play_symbol<const P: char, const N: u8>(
+    led: &mut Pin<P, N, Output>,
+    delay: &mut impl embedded_hal::delay::DelayNs,
+    symbol: Symbol,
+) {
+    let on_time = match symbol {
+        Symbol::Dot => UNIT_MS,
+        Symbol::Dash => 3 * UNIT_MS,
+    };
+
+    led.set_low(); // LED ON (inverted logic on most boards)
+    delay.delay_ms(on_time);
+    led.set_high(); // LED OFF
+}
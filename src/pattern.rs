@@ -0,0 +1,91 @@
+//! A small declarative engine for the LED patterns the button cycles through.
+//!
+//! Patterns are plain data (a [`Pattern`] variant), so adding or tweaking a
+//! sequence is a data edit rather than new control-flow code in `main`. Even
+//! Morse playback is just a variant here, so it can live in the same
+//! `PATTERNS` table as the on/off blink sequences instead of a hardcoded
+//! match arm. The PWM breathing fade and the multi-pin LED chaser stay out
+//! of the table and are driven directly from `main`: they don't toggle a
+//! single LED on a timer, so there's no `Step` they could be expressed as.
+
+use embedded_hal::delay::DelayNs;
+use stm32f4xx_hal::gpio::{Output, Pin};
+
+/// One step of a blink pattern.
+pub enum Step {
+    /// LED on for the given number of milliseconds.
+    On(u32),
+    /// LED off for the given number of milliseconds.
+    Off(u32),
+    /// Replay `steps` `count` times.
+    Repeat(u16, &'static [Step]),
+}
+
+/// A named sequence, either plain on/off steps or a Morse message.
+pub enum Pattern {
+    /// A sequence of [`Step`]s, run once in order.
+    Steps(&'static [Step]),
+    /// A message to blink out in Morse code, followed by a pause in
+    /// milliseconds before the sequence repeats.
+    Morse(&'static str, u32),
+}
+
+pub const FAST_BLINK: Pattern = Pattern::Steps(&[
+    Step::Repeat(3, &[Step::On(100), Step::Off(100)]),
+    Step::Off(1000), // Pause between sequences
+]);
+
+pub const SLOW_BLINK: Pattern = Pattern::Steps(&[
+    Step::Repeat(2, &[Step::On(500), Step::Off(500)]),
+    Step::Off(1000), // Pause between sequences
+]);
+
+pub const SOS: Pattern = Pattern::Morse("SOS", 2000);
+
+/// The table-driven patterns, indexed by `SEQUENCE_INDEX` (0 to 2); the
+/// chaser and breathing sequences that follow them are run from `main`.
+pub const PATTERNS: [Pattern; 3] = [FAST_BLINK, SLOW_BLINK, SOS];
+
+/// Runs `pattern` once, driving `led` high/low (or through the Morse encoder)
+/// for its duration.
+pub fn run_pattern<const P: char, const N: u8>(
+    led: &mut Pin<P, N, Output>,
+    delay: &mut impl DelayNs,
+    pattern: &Pattern,
+) {
+    match pattern {
+        Pattern::Steps(steps) => {
+            for step in *steps {
+                run_step(led, delay, step);
+            }
+        }
+        Pattern::Morse(msg, pause_ms) => {
+            crate::morse::blink_morse(led, delay, msg);
+            delay.delay_ms(*pause_ms);
+        }
+    }
+}
+
+fn run_step<const P: char, const N: u8>(
+    led: &mut Pin<P, N, Output>,
+    delay: &mut impl DelayNs,
+    step: &Step,
+) {
+    match step {
+        Step::On(ms) => {
+            led.set_low(); // LED ON (inverted logic on most boards)
+            delay.delay_ms(*ms);
+        }
+        Step::Off(ms) => {
+            led.set_high(); // LED OFF
+            delay.delay_ms(*ms);
+        }
+        Step::Repeat(count, steps) => {
+            for _ in 0..*count {
+                for inner in *steps {
+                    run_step(led, delay, inner);
+                }
+            }
+        }
+    }
+}